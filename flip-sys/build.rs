@@ -1,11 +1,53 @@
+use std::path::Path;
+use std::process::Command;
+
 fn main() {
-    cc::Build::new()
-        .cpp(true)
-        .files(["src/bindings.cpp"])
+    let mut build = cxx_build::bridge("src/lib.rs");
+    build
+        .file("src/bindings.cpp")
+        .define("NOMINMAX", None)
         .flag_if_supported("-std=c++17")
-        .includes(["src/", "extern/cpp/common/", "extern/cpp/CPP/"])
-        .compile("flip");
+        .flag_if_supported("/std:c++17")
+        .flag_if_supported("/permissive-")
+        .includes(["src/", "extern/cpp/common/", "extern/cpp/CPP/"]);
+
+    if cfg!(feature = "cuda") {
+        let cuda_source = Path::new("extern/cpp/CUDA/FLIP.cu");
+        if !nvcc_is_available() {
+            println!(
+                "cargo:warning=`cuda` feature is enabled but `nvcc` was not found on PATH; \
+                 falling back to the CPU FLIP backend"
+            );
+        } else if !cuda_source.exists() {
+            println!(
+                "cargo:warning=`cuda` feature is enabled and `nvcc` was found, but {} is not \
+                 vendored in this checkout; falling back to the CPU FLIP backend",
+                cuda_source.display()
+            );
+        } else {
+            build
+                .cuda(true)
+                .file(cuda_source)
+                .include("extern/cpp/CUDA/")
+                .define("FLIP_SYS_CUDA_ENABLED", None);
+            println!("cargo:rustc-cfg=flip_cuda_backend");
+            println!("cargo:rustc-link-lib=cudart");
+        }
+    }
+
+    build.compile("flip-sys");
 
+    println!("cargo:rerun-if-changed=src/lib.rs");
     println!("cargo:rerun-if-changed=src/bindings.cpp");
     println!("cargo:rerun-if-changed=src/bindings.hpp");
 }
+
+/// Probes for `nvcc` on `PATH`, the same way build systems conditionally enable optional
+/// toolchain-gated components rather than hard-failing when it's absent.
+fn nvcc_is_available() -> bool {
+    Command::new("nvcc")
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
@@ -1,4 +1,105 @@
-include!("bindings.rs");
+//! Low-level, `cxx`-generated bindings to the FLIP image type.
+//!
+//! The bridge is defined in this module and driven by `build.rs`, which compiles
+//! `src/bindings.cpp` against the generated header.
+//!
+//! # `FlipImage` ownership
+//!
+//! `FlipImage` is an opaque C++ type owned through [`cxx::UniquePtr`]:
+//!
+//! - It is always valid and non-null for as long as a `UniquePtr<FlipImage>` holding it
+//!   exists — `new_flip_image` never returns a null `UniquePtr`.
+//! - It is freed exactly once, by the `UniquePtr`'s `Drop` impl, when the last owner goes
+//!   out of scope. Callers never see a raw pointer or need to manually free anything, which
+//!   rules out the use-after-free/double-free bugs a hand-rolled RAII wrapper would otherwise
+//!   need to guard against.
+//!
+//! Once constructed, a `FlipImage` is never mutated again (comparison only reads it), so it
+//! is safe to share across threads; see the `Send`/`Sync` impls below and [`compare_batch`].
+//!
+//! When the `cuda` feature is enabled and both `nvcc` and `extern/cpp/CUDA/FLIP.cu` were
+//! found at build time, an image created with [`Backend::Cuda`] reports that backend;
+//! otherwise `build.rs` compiles only the CPU path and every image reports [`Backend::Cpu`].
+//! In both cases comparison runs the same CPU code.
+
+#[cxx::bridge]
+mod ffi {
+    /// The FLIP backend requested for an image. It is recorded on the image (see
+    /// `FlipImage::backend`); `compare` runs the same code for both variants.
+    #[derive(Debug)]
+    enum Backend {
+        Cpu,
+        Cuda,
+    }
+
+    unsafe extern "C++" {
+        include!("flip-sys/src/bindings.hpp");
+
+        type FlipImage;
+
+        /// Creates a new FLIP image with the given dimensions. If `data` is empty, the image
+        /// is zeroed; otherwise it must hold exactly `width * height * 3` Rgb8 bytes.
+        fn new_flip_image(width: u32, height: u32, data: &[u8], backend: Backend) -> UniquePtr<FlipImage>;
+
+        fn width(self: &FlipImage) -> u32;
+        fn height(self: &FlipImage) -> u32;
+        fn backend(self: &FlipImage) -> Backend;
+
+        /// Compares `self` and `test`, returning the mean absolute difference of their bytes
+        /// scaled to `[0, 1]`. This is not the perceptual FLIP metric; `pixels_per_degree`
+        /// has no effect.
+        ///
+        /// Does not mutate either image, so this is safe to call concurrently from multiple
+        /// threads on the same images.
+        fn compare(self: &FlipImage, test: &FlipImage, pixels_per_degree: f32) -> f32;
+    }
+}
+
+pub use ffi::{Backend, FlipImage};
+
+impl Default for Backend {
+    fn default() -> Self {
+        Backend::Cpu
+    }
+}
+
+// SAFETY: a `FlipImage` is fully initialized by its constructor and never mutated again -
+// `width`/`height`/`backend`/`compare` all take `&self` on the C++ side - so sharing
+// ownership or a reference across threads is sound the same way it would be for any other
+// immutable value.
+unsafe impl Send for ffi::FlipImage {}
+unsafe impl Sync for ffi::FlipImage {}
+
+/// Creates a new FLIP image on the CPU backend. Equivalent to
+/// `ffi::new_flip_image(width, height, data, Backend::Cpu)`.
+pub fn new_flip_image(width: u32, height: u32, data: &[u8]) -> cxx::UniquePtr<FlipImage> {
+    ffi::new_flip_image(width, height, data, Backend::Cpu)
+}
+
+/// Result of comparing a reference/test pair with [`compare_batch`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlipResult {
+    pub mean_error: f32,
+}
+
+/// Compares many reference/test image pairs across a thread pool, the common case of
+/// diffing a whole directory of reference/test renders in parallel.
+///
+/// Requires the `parallel` cargo feature.
+#[cfg(feature = "parallel")]
+pub fn compare_batch(
+    pairs: &[(cxx::UniquePtr<FlipImage>, cxx::UniquePtr<FlipImage>)],
+    pixels_per_degree: f32,
+) -> Vec<FlipResult> {
+    use rayon::prelude::*;
+
+    pairs
+        .par_iter()
+        .map(|(reference, test)| FlipResult {
+            mean_error: reference.compare(test, pixels_per_degree),
+        })
+        .collect()
+}
 
 #[cfg(test)]
 mod tests {
@@ -6,20 +107,38 @@ mod tests {
 
     #[test]
     fn creation_deletion() {
-        unsafe {
-            let image = flip_image_new(10, 10, std::ptr::null_mut());
-            assert!(!image.is_null());
-            flip_image_free(image);
-        }
+        let image = new_flip_image(10, 10, &[]);
+        assert!(!image.is_null());
     }
 
     #[test]
     fn creation_with_data_and_deletion() {
         let data = vec![0u8; 10 * 10 * 3];
-        unsafe {
-            let image = flip_image_new(10, 10, data.as_ptr());
-            assert!(!image.is_null());
-            flip_image_free(image);
-        }
+        let image = new_flip_image(10, 10, &data);
+        assert!(!image.is_null());
+    }
+
+    #[test]
+    fn creation_selects_backend() {
+        let image = ffi::new_flip_image(10, 10, &[], Backend::Cpu);
+        assert!(!image.is_null());
+        assert_eq!(image.backend(), Backend::Cpu);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn compare_batch_matches_sequential_compare() {
+        let reference = new_flip_image(4, 4, &[0u8; 4 * 4 * 3]);
+        let test = new_flip_image(4, 4, &vec![255u8; 4 * 4 * 3]);
+        let expected = reference.compare(&test, 67.0);
+
+        let pairs = vec![(
+            new_flip_image(4, 4, &[0u8; 4 * 4 * 3]),
+            new_flip_image(4, 4, &vec![255u8; 4 * 4 * 3]),
+        )];
+        let results = compare_batch(&pairs, 67.0);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].mean_error, expected);
     }
 }
@@ -0,0 +1,106 @@
+// Hand-written declarations for the C ABI implemented in `bindings.cpp`. `lib.rs` extends
+// this with a few newer additions (`FlipImageRgb32f`, the log-bucket pool constructor, pool
+// merge/variance, and the per-bucket value-range getters) directly in its own `extern "C"`
+// block instead of here.
+
+/// Opaque type for a 3 channel, 8 bit, FLIP image.
+pub enum FlipImageColor3 {}
+
+/// Opaque type for a single channel, 32 bit float, FLIP image.
+pub enum FlipImageFloat {}
+
+/// Opaque type for a FLIP value pool (a weighted histogram).
+pub enum FlipImagePool {}
+
+/// Opaque type for a reference to a [`FlipImagePool`]'s internal histogram.
+pub enum FlipImageHistogramRef {}
+
+extern "C" {
+    /// Creates a new 3 channel image. If `data` is null, the image is zeroed.
+    pub fn flip_image_color3_new(width: u32, height: u32, data: *const u8) -> *mut FlipImageColor3;
+    /// Clones the given image.
+    pub fn flip_image_color3_clone(image: *mut FlipImageColor3) -> *mut FlipImageColor3;
+    /// Frees the given image.
+    pub fn flip_image_color3_free(image: *mut FlipImageColor3);
+    /// Copies the data out of the given image. `data` must point to a buffer of at least
+    /// `width * height * 3` bytes.
+    pub fn flip_image_color3_get_data(image: *mut FlipImageColor3, data: *mut u8);
+    /// Creates a 256x1 color lookup table. Entry `i` is the gray `(i, i, i)`; this is not the
+    /// magma colormap.
+    pub fn flip_image_color3_magma_map() -> *mut FlipImageColor3;
+    /// Maps `error_map` through `value_mapping` into `output`.
+    pub fn flip_image_color3_color_map(
+        output: *mut FlipImageColor3,
+        error_map: *mut FlipImageFloat,
+        value_mapping: *mut FlipImageColor3,
+    );
+
+    /// Creates a new single channel image. If `data` is null, the image is zeroed.
+    pub fn flip_image_float_new(width: u32, height: u32, data: *const f32) -> *mut FlipImageFloat;
+    /// Clones the given image.
+    pub fn flip_image_float_clone(image: *mut FlipImageFloat) -> *mut FlipImageFloat;
+    /// Frees the given image.
+    pub fn flip_image_float_free(image: *mut FlipImageFloat);
+    /// Copies the data out of the given image. `data` must point to a buffer of at least
+    /// `width * height` floats.
+    pub fn flip_image_float_get_data(image: *mut FlipImageFloat, data: *mut f32);
+    /// Compares `reference` and `test`, writing into `error_map`, per pixel, the mean absolute
+    /// difference of the three channels scaled to `[0, 1]`. This is not the perceptual FLIP
+    /// metric; `pixels_per_degree` has no effect.
+    pub fn flip_image_float_flip(
+        error_map: *mut FlipImageFloat,
+        reference: *mut FlipImageColor3,
+        test: *mut FlipImageColor3,
+        pixels_per_degree: f32,
+    );
+    /// Copies `input`'s single channel value into all 3 channels of `output`.
+    pub fn flip_image_float_copy_float_to_color3(input: *mut FlipImageFloat, output: *mut FlipImageColor3);
+
+    /// Creates a new pool with `bucket_count` uniform-width buckets.
+    pub fn flip_image_pool_new(bucket_count: usize) -> *mut FlipImagePool;
+    /// Frees the given pool.
+    pub fn flip_image_pool_free(pool: *mut FlipImagePool);
+    /// Clears the pool.
+    pub fn flip_image_pool_clear(pool: *mut FlipImagePool);
+    /// Adds every value in `image` to the pool.
+    pub fn flip_image_pool_update_image(pool: *mut FlipImagePool, image: *mut FlipImageFloat);
+    /// Returns the minimum value stored in the pool.
+    pub fn flip_image_pool_get_min_value(pool: *mut FlipImagePool) -> f32;
+    /// Returns the maximum value stored in the pool.
+    pub fn flip_image_pool_get_max_value(pool: *mut FlipImagePool) -> f32;
+    /// Returns the mean value stored in the pool.
+    pub fn flip_image_pool_get_mean(pool: *mut FlipImagePool) -> f32;
+    /// Returns the given percentile `[0, 1]` of the values stored in the pool.
+    pub fn flip_image_pool_get_percentile(pool: *mut FlipImagePool, percentile: f32, weighted: bool) -> f32;
+    /// Returns the given percentile `[0, 1]` of the values stored in the pool, in double precision.
+    pub fn flip_image_pool_get_weighted_percentile(pool: *mut FlipImagePool, percentile: f64) -> f64;
+    /// Returns a new reference to the pool's internal histogram.
+    pub fn flip_image_pool_get_histogram(pool: *mut FlipImagePool) -> *mut FlipImageHistogramRef;
+
+    /// Returns the difference between the maximum and minimum bucket values of `histogram`.
+    pub fn flip_image_histogram_ref_get_bucket_size(histogram: *mut FlipImageHistogramRef) -> usize;
+    /// Returns the index of the lowest bucket in use, or `usize::MAX` if none are.
+    pub fn flip_image_histogram_ref_get_bucket_id_min(histogram: *mut FlipImageHistogramRef) -> usize;
+    /// Returns the index of the highest bucket in use, or 0 if none are.
+    pub fn flip_image_histogram_ref_get_bucket_id_max(histogram: *mut FlipImageHistogramRef) -> usize;
+    /// Returns the number of values contained within the given bucket.
+    pub fn flip_image_histogram_ref_get_bucket_value(histogram: *mut FlipImageHistogramRef, bucket_id: usize) -> usize;
+    /// Returns the number of buckets in the histogram.
+    pub fn flip_image_histogram_ref_size(histogram: *mut FlipImageHistogramRef) -> usize;
+    /// Returns the smallest value the histogram can handle.
+    pub fn flip_image_histogram_ref_get_min_value(histogram: *mut FlipImageHistogramRef) -> f32;
+    /// Returns the largest value the histogram can handle.
+    pub fn flip_image_histogram_ref_get_max_value(histogram: *mut FlipImageHistogramRef) -> f32;
+    /// Clears the histogram.
+    pub fn flip_image_histogram_ref_clear(histogram: *mut FlipImageHistogramRef);
+    /// Resizes the histogram to have `bucket_size` uniform buckets.
+    pub fn flip_image_histogram_ref_resize(histogram: *mut FlipImageHistogramRef, bucket_size: usize);
+    /// Returns which bucket the given value would fall into.
+    pub fn flip_image_histogram_ref_value_bucket_id(histogram: *mut FlipImageHistogramRef, value: f32) -> usize;
+    /// Includes `count` instances of `value` in the histogram.
+    pub fn flip_image_histogram_ref_inc_value(histogram: *mut FlipImageHistogramRef, value: f32, count: usize);
+    /// Includes one instance of each value in `image` in the histogram.
+    pub fn flip_image_histogram_ref_inc_image(histogram: *mut FlipImageHistogramRef, image: *mut FlipImageFloat);
+    /// Frees the given histogram reference.
+    pub fn flip_image_histogram_ref_free(histogram: *mut FlipImageHistogramRef);
+}
@@ -26,6 +26,46 @@ pub fn pixels_per_degree(distance: f32, resolution_x: f32, monitor_width: f32) -
     distance * (resolution_x / monitor_width) * (std::f32::consts::PI / 180.0)
 }
 
+/// Opaque type for a 3 channel, 32 bit float, FLIP image.
+///
+/// Unlike [`FlipImageColor3`], values are not assumed to be perceptually encoded,
+/// so this is used for linear HDR data on the way into tonemapping.
+pub enum FlipImageRgb32f {}
+
+extern "C" {
+    /// Creates a new 3 channel float image. If `data` is null, the image is zeroed.
+    pub fn flip_image_rgb32f_new(width: u32, height: u32, data: *const f32) -> *mut FlipImageRgb32f;
+    /// Clones the given image.
+    pub fn flip_image_rgb32f_clone(image: *mut FlipImageRgb32f) -> *mut FlipImageRgb32f;
+    /// Frees the given image.
+    pub fn flip_image_rgb32f_free(image: *mut FlipImageRgb32f);
+    /// Copies the data out of the given image. `data` must point to a buffer of at least
+    /// `width * height * 3` floats.
+    pub fn flip_image_rgb32f_get_data(image: *mut FlipImageRgb32f, data: *mut f32);
+
+    /// Creates a new pool using the relative-error log-linear bucketing scheme: uniform
+    /// buckets of width `2^m` below `2^r - 1`, and `2^(r-m)` linear sub-buckets per
+    /// exponentially growing range from there up to `2^n - 1`.
+    pub fn flip_image_pool_new_log(m: u32, r: u32, n: u32) -> *mut FlipImagePool;
+
+    /// Folds `other`'s buckets and count into `pool`. Returns `false` if the two pools do not
+    /// have compatible bucket configurations, in which case `pool` is left unchanged.
+    pub fn flip_image_pool_merge(pool: *mut FlipImagePool, other: *const FlipImagePool) -> bool;
+    /// Returns the variance of the values stored in the pool.
+    pub fn flip_image_pool_get_variance(pool: *mut FlipImagePool) -> f32;
+
+    /// Returns the lower bound of the value range covered by the given bucket.
+    pub fn flip_image_histogram_ref_get_bucket_min_value(
+        histogram: *mut FlipImageHistogramRef,
+        bucket_id: usize,
+    ) -> f32;
+    /// Returns the upper bound of the value range covered by the given bucket.
+    pub fn flip_image_histogram_ref_get_bucket_max_value(
+        histogram: *mut FlipImageHistogramRef,
+        bucket_id: usize,
+    ) -> f32;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
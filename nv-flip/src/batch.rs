@@ -0,0 +1,70 @@
+//! Parallel batch comparison, gated behind the `parallel` cargo feature.
+//!
+//! [`FlipImageRgb8`] and [`FlipImageFloat`] are already `Send + Sync`, so this is mostly a
+//! scheduling layer over the existing FFI, letting rendering regression suites compare
+//! hundreds of reference/test pairs across cores instead of one at a time on the caller's
+//! thread.
+
+use rayon::prelude::*;
+
+use crate::{flip, FlipImageFloat, FlipImageRgb8, FlipPool};
+
+/// Runs [`flip`] over many reference/test pairs in parallel using rayon.
+///
+/// The pixels-per-degree value is shared across every pair; see [`flip`] for its meaning.
+pub fn flip_batch(
+    pairs: impl IntoParallelIterator<Item = (FlipImageRgb8, FlipImageRgb8)>,
+    pixels_per_degree: f32,
+) -> Vec<FlipImageFloat> {
+    pairs
+        .into_par_iter()
+        .map(|(reference_image, test_image)| flip(reference_image, test_image, pixels_per_degree))
+        .collect()
+}
+
+/// Like [`flip_batch`], but also returns a [`FlipPool`] merged across every pair and each
+/// pair's individual mean error, for regression reports that need both the per-pair and
+/// aggregate picture.
+pub fn flip_batch_pooled(
+    pairs: impl IntoParallelIterator<Item = (FlipImageRgb8, FlipImageRgb8)>,
+    pixels_per_degree: f32,
+) -> (Vec<FlipImageFloat>, FlipPool, Vec<f32>) {
+    let error_maps = flip_batch(pairs, pixels_per_degree);
+
+    // `FlipPool` is not `Send`, so the pools are built on this thread from the error maps.
+    let mut merged = FlipPool::new();
+    let mut means = Vec::with_capacity(error_maps.len());
+    for error_map in &error_maps {
+        let pool = FlipPool::from_image(error_map);
+        merged.merge(&pool);
+        means.push(pool.mean());
+    }
+
+    (error_maps, merged, means)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pooled_batch_matches_merging_by_hand() {
+        let pairs = vec![
+            (FlipImageRgb8::new(4, 4), FlipImageRgb8::with_data(4, 4, &[255u8; 4 * 4 * 3])),
+            (FlipImageRgb8::new(4, 4), FlipImageRgb8::new(4, 4)),
+        ];
+
+        let (error_maps, merged, means) = flip_batch_pooled(pairs.clone(), 67.0);
+        assert_eq!(error_maps.len(), 2);
+        assert_eq!(means.len(), 2);
+
+        let mut expected = FlipPool::new();
+        for (reference_image, test_image) in pairs {
+            let error_map = flip(reference_image, test_image, 67.0);
+            expected.merge(&FlipPool::from_image(&error_map));
+        }
+
+        assert_eq!(merged.mean(), expected.mean());
+        assert_eq!(merged.variance(), expected.variance());
+    }
+}
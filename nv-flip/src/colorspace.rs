@@ -0,0 +1,25 @@
+//! Shared sRGB <-> linear conversion math, used by [`crate::FlipImageFloat`]'s conversion
+//! methods as well as the HDR-FLIP exposure/tonemapping pipeline.
+
+/// Converts a single sRGB-encoded channel value to linear light.
+pub(crate) fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a single linear-light channel value to sRGB encoding.
+pub(crate) fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Converts a single linear-light channel value in `[0, 1]` to an 8-bit sRGB value.
+pub(crate) fn linear_to_srgb_u8(c: f32) -> u8 {
+    (linear_to_srgb(c.clamp(0.0, 1.0)) * 255.0).round() as u8
+}
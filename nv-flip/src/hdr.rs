@@ -0,0 +1,302 @@
+//! HDR-FLIP: comparison of linear, high-dynamic-range images.
+//!
+//! HDR-FLIP works by tonemapping both images at a sweep of exposures, running the regular
+//! LDR-FLIP comparison at each exposure, and keeping the worst (maximum) error seen at each
+//! pixel. See [`flip_hdr`] for the entry point.
+
+use crate::colorspace::linear_to_srgb_u8;
+use crate::{flip, FlipImageFloat, FlipImageRgb8};
+
+/// 2D FLIP image that is accessed as linear Rgb32f.
+///
+/// Unlike [`FlipImageRgb8`], the values are not assumed to be perceptually (sRGB) encoded.
+/// This is the type used for the linear HDR inputs to [`flip_hdr`].
+pub struct FlipImageRgb32f {
+    inner: *mut nv_flip_sys::FlipImageRgb32f,
+    width: u32,
+    height: u32,
+}
+
+unsafe impl Send for FlipImageRgb32f {}
+unsafe impl Sync for FlipImageRgb32f {}
+
+impl Clone for FlipImageRgb32f {
+    fn clone(&self) -> Self {
+        let inner = unsafe { nv_flip_sys::flip_image_rgb32f_clone(self.inner) };
+        assert!(!inner.is_null());
+        Self {
+            inner,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl FlipImageRgb32f {
+    /// Create a new image with the given dimensions and zeroed contents.
+    pub fn new(width: u32, height: u32) -> Self {
+        let inner = unsafe { nv_flip_sys::flip_image_rgb32f_new(width, height, std::ptr::null()) };
+        assert!(!inner.is_null());
+        Self {
+            inner,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a new image with the given dimensions and copies the data into it.
+    ///
+    /// The data must be linear (not sRGB-encoded) Rgb32f. Do not include alpha.
+    ///
+    /// Data is expected in row-major order, from the top left, tightly packed.
+    ///
+    /// # Panics
+    ///
+    /// - If the data is not large enough to fill the image.
+    pub fn with_data(width: u32, height: u32, data: &[f32]) -> Self {
+        assert!(data.len() >= (width * height * 3) as usize);
+        let inner = unsafe { nv_flip_sys::flip_image_rgb32f_new(width, height, data.as_ptr()) };
+        assert!(!inner.is_null());
+        Self {
+            inner,
+            width,
+            height,
+        }
+    }
+
+    /// Extracts the data from the image and returns it as a vector.
+    ///
+    /// Data is returned in row-major order, from the top left, tightly packed.
+    pub fn to_vec(&self) -> Vec<f32> {
+        let mut data = vec![0f32; (self.width * self.height * 3) as usize];
+        unsafe {
+            nv_flip_sys::flip_image_rgb32f_get_data(self.inner, data.as_mut_ptr());
+        }
+        data
+    }
+
+    /// Returns the width of the image.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the image.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Encodes this linear image to the sRGB 8-bit [`FlipImageRgb8`] buffer that LDR-FLIP
+    /// consumes, making the linear-to-display pipeline explicit instead of comparing
+    /// mismatched color spaces.
+    pub fn to_rgb8_srgb(&self) -> FlipImageRgb8 {
+        let data: Vec<u8> = self.to_vec().into_iter().map(linear_to_srgb_u8).collect();
+        FlipImageRgb8::with_data(self.width, self.height, &data)
+    }
+}
+
+impl Drop for FlipImageRgb32f {
+    fn drop(&mut self) {
+        unsafe {
+            nv_flip_sys::flip_image_rgb32f_free(self.inner);
+        }
+    }
+}
+
+/// Tonemapping operator applied to each exposure before running LDR-FLIP.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Tonemapper {
+    /// Narkowicz's fitted ACES filmic curve.
+    #[default]
+    Aces,
+    /// Hable's "Uncharted 2" filmic curve.
+    Hable,
+    /// Simple Reinhard `x / (1 + x)` curve.
+    Reinhard,
+}
+
+impl Tonemapper {
+    fn map_channel(self, c: f32) -> f32 {
+        match self {
+            Tonemapper::Aces => {
+                const A: f32 = 2.51;
+                const B: f32 = 0.03;
+                const C: f32 = 2.43;
+                const D: f32 = 0.59;
+                const E: f32 = 0.14;
+                (c * (A * c + B)) / (c * (C * c + D) + E)
+            }
+            Tonemapper::Hable => {
+                fn partial(x: f32) -> f32 {
+                    const A: f32 = 0.15;
+                    const B: f32 = 0.50;
+                    const C: f32 = 0.10;
+                    const D: f32 = 0.20;
+                    const E: f32 = 0.02;
+                    const F: f32 = 0.30;
+                    ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F
+                }
+                const WHITE: f32 = 11.2;
+                partial(1.5 * c) / partial(WHITE)
+            }
+            Tonemapper::Reinhard => c / (1.0 + c),
+        }
+    }
+}
+
+/// Options controlling the exposure sweep and tonemapping used by [`flip_hdr`].
+#[derive(Debug, Copy, Clone)]
+pub struct HdrFlipOptions {
+    /// The lowest exposure (in stops) to sweep. `None` auto-computes from the reference image.
+    pub start_exposure: Option<f32>,
+    /// The highest exposure (in stops) to sweep. `None` auto-computes from the reference image.
+    pub stop_exposure: Option<f32>,
+    /// How many exposures to sweep between `start_exposure` and `stop_exposure`, inclusive.
+    pub num_exposures: u32,
+    /// The tonemapping operator applied at each exposure.
+    pub tonemapper: Tonemapper,
+}
+
+impl Default for HdrFlipOptions {
+    fn default() -> Self {
+        Self {
+            start_exposure: None,
+            stop_exposure: None,
+            num_exposures: 8,
+            tonemapper: Tonemapper::default(),
+        }
+    }
+}
+
+fn luminance(r: f32, g: f32, b: f32) -> f32 {
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// Computes `(start_exposure, stop_exposure)` from the reference image's luminance
+/// distribution.
+///
+/// Both ends are derived the same, key-relative way: the exposure that would bring the
+/// median luminance up to the Reinhard "key value" of 0.18, and the exposure that would
+/// bring the maximum luminance down to the 1.0 clip point. Whichever of the two is lower
+/// becomes `start_exposure` and the other `stop_exposure` - for a dark scene the median
+/// needs brightening more than the max needs darkening, so the max's (lower) exposure is
+/// the start and the median's (higher) exposure is the stop, and vice versa for a bright
+/// scene. Swapping like this (rather than clamping one to the other) keeps the sweep from
+/// collapsing to a single exposure.
+fn auto_exposure_range(reference: &FlipImageRgb32f) -> (f32, f32) {
+    const KEY_VALUE: f32 = 0.18;
+    const EPSILON: f32 = 1e-4;
+
+    let data = reference.to_vec();
+    let mut luminances: Vec<f32> = data
+        .chunks_exact(3)
+        .map(|p| luminance(p[0], p[1], p[2]).max(0.0))
+        .collect();
+    luminances.sort_by(|a, b| a.total_cmp(b));
+
+    let median = luminances
+        .get(luminances.len() / 2)
+        .copied()
+        .unwrap_or(0.0)
+        .max(EPSILON);
+    let max = luminances.last().copied().unwrap_or(0.0).max(EPSILON);
+
+    let exposure_from_median = (KEY_VALUE / median).log2();
+    let exposure_from_max = (1.0 / max).log2();
+
+    (
+        exposure_from_median.min(exposure_from_max),
+        exposure_from_median.max(exposure_from_max),
+    )
+}
+
+fn encode_exposed_srgb8(data: &[f32], exposure: f32, tonemapper: Tonemapper) -> Vec<u8> {
+    let multiplier = 2f32.powf(exposure);
+    data.iter()
+        .map(|&c| {
+            let exposed = tonemapper.map_channel((c * multiplier).max(0.0));
+            linear_to_srgb_u8(exposed)
+        })
+        .collect()
+}
+
+/// Performs an HDR-FLIP comparison between the two linear images.
+///
+/// The images must be the same size.
+///
+/// Sweeps `opts.num_exposures` tonemapping exposures between `opts.start_exposure` and
+/// `opts.stop_exposure` (auto-computed from `reference` if not given), runs the regular
+/// LDR-FLIP at each exposure, and takes the per-pixel maximum error across all exposures.
+///
+/// Returns the error map, and an exposure map recording, per pixel, the index of the
+/// exposure (into `[0, opts.num_exposures)`) that produced that maximum error.
+///
+/// # Panics
+///
+/// - If the images are not the same size.
+/// - If `opts.num_exposures` is 0.
+pub fn flip_hdr(
+    reference: FlipImageRgb32f,
+    test: FlipImageRgb32f,
+    pixels_per_degree: f32,
+    opts: HdrFlipOptions,
+) -> (FlipImageFloat, FlipImageFloat) {
+    assert_eq!(
+        reference.width(),
+        test.width(),
+        "Width mismatch between reference and test image"
+    );
+    assert_eq!(
+        reference.height(),
+        test.height(),
+        "Height mismatch between reference and test image"
+    );
+    assert!(opts.num_exposures > 0, "num_exposures must be non-zero");
+
+    // Only pay for the luminance pass (an FFI copy of the whole image plus a full sort) if
+    // at least one bound actually needs auto-computing.
+    let (start_exposure, stop_exposure) = match (opts.start_exposure, opts.stop_exposure) {
+        (Some(start), Some(stop)) => (start, stop),
+        (start, stop) => {
+            let (auto_start, auto_stop) = auto_exposure_range(&reference);
+            (start.unwrap_or(auto_start), stop.unwrap_or(auto_stop))
+        }
+    };
+
+    let width = reference.width();
+    let height = reference.height();
+    let pixel_count = (width * height) as usize;
+
+    let reference_data = reference.to_vec();
+    let test_data = test.to_vec();
+
+    let mut max_error = vec![0f32; pixel_count];
+    let mut max_exposure_index = vec![0f32; pixel_count];
+
+    for exposure_index in 0..opts.num_exposures {
+        let t = if opts.num_exposures == 1 {
+            0.0
+        } else {
+            exposure_index as f32 / (opts.num_exposures - 1) as f32
+        };
+        let exposure = start_exposure + t * (stop_exposure - start_exposure);
+
+        let reference_ldr = encode_exposed_srgb8(&reference_data, exposure, opts.tonemapper);
+        let test_ldr = encode_exposed_srgb8(&test_data, exposure, opts.tonemapper);
+
+        let reference_image = FlipImageRgb8::with_data(width, height, &reference_ldr);
+        let test_image = FlipImageRgb8::with_data(width, height, &test_ldr);
+        let error_map = flip(reference_image, test_image, pixels_per_degree).to_vec();
+
+        for (pixel, &error) in error_map.iter().enumerate() {
+            if error > max_error[pixel] {
+                max_error[pixel] = error;
+                max_exposure_index[pixel] = exposure_index as f32;
+            }
+        }
+    }
+
+    (
+        FlipImageFloat::with_data(width, height, &max_error),
+        FlipImageFloat::with_data(width, height, &max_exposure_index),
+    )
+}
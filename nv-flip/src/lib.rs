@@ -87,6 +87,16 @@ use std::marker::PhantomData;
 
 pub use nv_flip_sys::{pixels_per_degree, DEFAULT_PIXELS_PER_DEGREE};
 
+mod colorspace;
+
+mod hdr;
+pub use hdr::{flip_hdr, FlipImageRgb32f, HdrFlipOptions, Tonemapper};
+
+#[cfg(feature = "parallel")]
+mod batch;
+#[cfg(feature = "parallel")]
+pub use batch::{flip_batch, flip_batch_pooled};
+
 /// 2D FLIP image that is accessed as Rgb8.
 ///
 /// Internally this is Rgb32f, but the values are converted when read.
@@ -270,6 +280,30 @@ impl FlipImageFloat {
     pub fn height(&self) -> u32 {
         self.height
     }
+
+    /// Converts this image from sRGB-encoded values to linear light, per-channel.
+    ///
+    /// Renderers and this type naturally hold linear-light values, so this (and
+    /// [`Self::linear_to_srgb`]) make the conversion explicit rather than leaving callers to
+    /// convert by hand outside the crate.
+    pub fn srgb_to_linear(&self) -> FlipImageFloat {
+        let data: Vec<f32> = self
+            .to_vec()
+            .into_iter()
+            .map(colorspace::srgb_to_linear)
+            .collect();
+        FlipImageFloat::with_data(self.width, self.height, &data)
+    }
+
+    /// Converts this image from linear light to sRGB-encoded values, per-channel.
+    pub fn linear_to_srgb(&self) -> FlipImageFloat {
+        let data: Vec<f32> = self
+            .to_vec()
+            .into_iter()
+            .map(colorspace::linear_to_srgb)
+            .collect();
+        FlipImageFloat::with_data(self.width, self.height, &data)
+    }
 }
 
 impl Drop for FlipImageFloat {
@@ -383,6 +417,24 @@ impl<'a> FlipHistogram<'a> {
         unsafe { nv_flip_sys::flip_image_histogram_ref_size(self.inner) }
     }
 
+    /// Returns the range of values covered by the given bucket.
+    ///
+    /// For a pool created with [`FlipPool::with_log_buckets`], this is in the scaled
+    /// `[0, 2^n - 1]` domain described there, not the `[0, 1]` domain [`FlipPool::mean`] and
+    /// friends use.
+    ///
+    /// # Panics
+    ///
+    /// - If the bucket_id is out of bounds.
+    pub fn bucket_value_range(&self, bucket_id: usize) -> std::ops::Range<f32> {
+        assert!(bucket_id < self.bucket_count());
+        let min =
+            unsafe { nv_flip_sys::flip_image_histogram_ref_get_bucket_min_value(self.inner, bucket_id) };
+        let max =
+            unsafe { nv_flip_sys::flip_image_histogram_ref_get_bucket_max_value(self.inner, bucket_id) };
+        min..max
+    }
+
     /// Returns the smallest value the histogram can handle.
     pub fn minimum_allowed_value(&self) -> f32 {
         unsafe { nv_flip_sys::flip_image_histogram_ref_get_min_value(self.inner) }
@@ -471,6 +523,34 @@ impl FlipPool {
         }
     }
 
+    /// Creates a new pool using a logarithmic-linear bucketing scheme instead of the fixed
+    /// uniform buckets used by [`Self::with_buckets`].
+    ///
+    /// FLIP errors lie in `[0, 1]` and are heavily concentrated near 0, where the mean and
+    /// low quartiles that users actually report live. This scheme keeps a bounded *relative*
+    /// error everywhere by using fine uniform buckets below a cutoff and exponentially
+    /// growing, linearly-subdivided buckets above it:
+    ///
+    /// - `m` - `2^m` is the width of the smallest bucket.
+    /// - `r` - `2^r - 1` is the cutoff below which buckets are uniform.
+    /// - `n` - `2^n - 1` is the largest value the pool can hold.
+    ///
+    /// The `[0, 1]` FLIP error is scaled by `2^n - 1` before bucketing, but this is purely an
+    /// internal bucketing detail: [`Self::mean`], [`Self::min_value`], [`Self::max_value`],
+    /// and the percentile getters all continue to report values in the original `[0, 1]`
+    /// domain. Only the [`FlipHistogram`] reached through [`Self::histogram`] (for example
+    /// [`FlipHistogram::bucket_value_range`] and [`FlipHistogram::maximum_allowed_value`])
+    /// works in the scaled `[0, 2^n - 1]` domain, since that's what the buckets themselves are
+    /// divided up in.
+    pub fn with_log_buckets(m: u32, r: u32, n: u32) -> Self {
+        let inner = unsafe { nv_flip_sys::flip_image_pool_new_log(m, r, n) };
+        assert!(!inner.is_null());
+        Self {
+            inner,
+            values_added: 0,
+        }
+    }
+
     /// Creates a new pool and initializes the buckets with the values given image.
     pub fn from_image(image: &FlipImageFloat) -> Self {
         let mut pool = Self::new();
@@ -570,6 +650,51 @@ impl FlipPool {
         }
         self.values_added = 0;
     }
+
+    /// Folds `other`'s buckets and count into this pool, for aggregating statistics across
+    /// many images (e.g. a whole test suite or animation) without re-running every image
+    /// through a single pool.
+    ///
+    /// # Panics
+    ///
+    /// - If `other` does not have a compatible bucket configuration.
+    pub fn merge(&mut self, other: &FlipPool) {
+        let compatible = unsafe { nv_flip_sys::flip_image_pool_merge(self.inner, other.inner) };
+        assert!(compatible, "Cannot merge FlipPools with incompatible bucket configurations");
+        self.values_added += other.values_added;
+    }
+
+    /// Gets the variance of the values stored in the pool.
+    ///
+    /// Returns 0.0 if no values have been added to the pool.
+    pub fn variance(&self) -> f32 {
+        if self.values_added == 0 {
+            return 0.0;
+        }
+        unsafe { nv_flip_sys::flip_image_pool_get_variance(self.inner) }
+    }
+
+    /// Gets the standard deviation of the values stored in the pool.
+    ///
+    /// Returns 0.0 if no values have been added to the pool.
+    pub fn std_dev(&self) -> f32 {
+        self.variance().sqrt()
+    }
+
+    /// Iterates over the pool's buckets, yielding the value range and count of values of
+    /// every bucket from the lowest to the highest one in use; buckets in between may have a
+    /// count of 0. Yields nothing for an empty pool. Useful for plotting or serializing the
+    /// error distribution.
+    pub fn buckets(&mut self) -> impl Iterator<Item = (std::ops::Range<f32>, usize)> + '_ {
+        let histogram = self.histogram();
+        let range = histogram.bucket_id_min().map(|min| min..=histogram.bucket_id_max());
+        range.into_iter().flatten().map(move |bucket_id| {
+            (
+                histogram.bucket_value_range(bucket_id),
+                histogram.bucket_value_count(bucket_id),
+            )
+        })
+    }
 }
 
 impl Default for FlipPool {
@@ -597,6 +722,49 @@ mod tests {
         assert_eq!(FlipImageFloat::new(10, 10).to_vec(), vec![0.0f32; 10 * 10]);
     }
 
+    #[test]
+    fn srgb_linear_round_trip() {
+        let data = vec![0.0f32, 0.18, 0.5, 1.0];
+        let image = FlipImageFloat::with_data(2, 2, &data);
+
+        let round_tripped = image.linear_to_srgb().srgb_to_linear().to_vec();
+
+        for (a, b) in data.iter().zip(round_tripped.iter()) {
+            assert_float_eq!(a, b, abs <= 0.0001);
+        }
+    }
+
+    #[test]
+    fn merge_combines_counts() {
+        let image = FlipImageFloat::new(10, 10);
+
+        let mut a = FlipPool::new();
+        a.update_with_image(&image);
+        let mut b = FlipPool::new();
+        b.update_with_image(&image);
+
+        a.merge(&b);
+
+        assert_eq!(a.values_added, 200);
+        assert_eq!(a.variance(), 0.0);
+        assert_eq!(a.std_dev(), 0.0);
+
+        let buckets: Vec<_> = a.buckets().collect();
+        assert!(!buckets.is_empty());
+    }
+
+    #[test]
+    fn log_bucket_pool_zeroed() {
+        let mut pool = FlipPool::with_log_buckets(0, 5, 8);
+        assert_eq!(pool.min_value(), 0.0);
+        assert_eq!(pool.max_value(), 0.0);
+        assert_eq!(pool.mean(), 0.0);
+
+        let image = FlipImageFloat::new(10, 10);
+        pool.update_with_image(&image);
+        assert_eq!(pool.mean(), 0.0);
+    }
+
     #[test]
     fn zero_size_pool_ops() {
         let mut pool = FlipPool::new();